@@ -0,0 +1,17 @@
+/// Whether the universe wraps around its edges (a torus) or treats everything past the
+/// edge as permanently dead (a finite plane)
+pub enum Boundary {
+    Toroidal,
+    Dead,
+}
+
+impl Boundary {
+    /// Parse a `--boundary` value: `torus` or `dead`
+    pub fn parse(s: &str) -> Option<Boundary> {
+        match s {
+            "torus" => Some(Boundary::Toroidal),
+            "dead" => Some(Boundary::Dead),
+            _ => None,
+        }
+    }
+}
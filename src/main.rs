@@ -1,7 +1,12 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
 use std::io;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use ratatui::{
     backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
     widgets::Paragraph,
     Frame, Terminal,
 };
@@ -12,6 +17,81 @@ use crossterm::{
 };
 use rand::Rng;
 
+mod boundary;
+mod pattern;
+mod rule;
+mod sparse;
+
+use boundary::Boundary;
+use rule::Rule;
+use sparse::SparseGrid;
+
+/// Command-line options for the simulation
+struct Args {
+    pattern: Option<PathBuf>,
+    rule: Rule,
+    universe: Option<(i64, i64)>,
+    auto_reseed: bool,
+    boundary: Boundary,
+}
+
+/// Parse the command-line arguments we care about, ignoring anything unrecognized
+fn parse_args() -> Args {
+    let mut args = Args {
+        pattern: None,
+        rule: Rule::conway(),
+        universe: None,
+        auto_reseed: false,
+        boundary: Boundary::Toroidal,
+    };
+    let mut it = std::env::args().skip(1);
+
+    while let Some(arg) = it.next() {
+        if arg == "--pattern" {
+            args.pattern = it.next().map(PathBuf::from);
+        } else if arg == "--rule" {
+            if let Some(value) = it.next() {
+                if let Some(rule) = Rule::parse(&value) {
+                    args.rule = rule;
+                }
+            }
+        } else if arg == "--universe" {
+            if let Some(value) = it.next() {
+                args.universe = parse_universe(&value);
+            }
+        } else if arg == "--auto-reseed" {
+            args.auto_reseed = true;
+        } else if arg == "--boundary" {
+            if let Some(value) = it.next() {
+                if let Some(boundary) = Boundary::parse(&value) {
+                    args.boundary = boundary;
+                }
+            }
+        }
+    }
+
+    args
+}
+
+/// Hash a grid's contents, used to detect still lifes and low-period oscillators
+fn hash_grid(grid: &[bool]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    grid.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Parse a `WIDTHxHEIGHT` universe size, e.g. `100000x100000`
+/// Both dimensions must be positive; `rem_euclid` panics on a zero divisor
+fn parse_universe(s: &str) -> Option<(i64, i64)> {
+    let (w, h) = s.split_once('x')?;
+    let width: i64 = w.trim().parse().ok()?;
+    let height: i64 = h.trim().parse().ok()?;
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+    Some((width, height))
+}
+
 fn pos(row: usize, col: usize, width: usize) -> usize {
     row * width + col
 }
@@ -23,9 +103,32 @@ fn pos_wrap(row: isize, col: isize, width: usize, height: usize) -> usize {
     pos(r, c, width)
 }
 
-/// Apply Game of Life rules at a given position
+/// Look up whether a (possibly out-of-bounds) neighbor coordinate is alive, per the
+/// chosen boundary mode
+fn neighbor_alive(grid: &[bool], row: isize, col: isize, width: usize, height: usize, boundary: &Boundary) -> bool {
+    match boundary {
+        Boundary::Toroidal => grid[pos_wrap(row, col, width, height)],
+        Boundary::Dead => {
+            if row < 0 || col < 0 || row >= height as isize || col >= width as isize {
+                false
+            } else {
+                grid[pos(row as usize, col as usize, width)]
+            }
+        }
+    }
+}
+
+/// Apply the given Life-like rule at a position
 /// Returns true if cell should be alive in next generation
-fn cell_next_state(grid: &[bool], row: isize, col: isize, width: usize, height: usize) -> bool {
+fn cell_next_state(
+    grid: &[bool],
+    row: isize,
+    col: isize,
+    width: usize,
+    height: usize,
+    rule: &Rule,
+    boundary: &Boundary,
+) -> bool {
     // Count live neighbors (8 surrounding cells)
     let mut neighbors = 0;
     for dr in -1..=1 {
@@ -33,40 +136,58 @@ fn cell_next_state(grid: &[bool], row: isize, col: isize, width: usize, height:
             if dr == 0 && dc == 0 {
                 continue;
             }
-            if grid[pos_wrap(row + dr, col + dc, width, height)] {
+            if neighbor_alive(grid, row + dr, col + dc, width, height, boundary) {
                 neighbors += 1;
             }
         }
     }
 
-    let alive = grid[pos_wrap(row, col, width, height)];
+    let alive = grid[pos(row as usize, col as usize, width)];
 
-    // Game of Life rules:
-    // 1. Live cell with 2 or 3 neighbors survives
-    // 2. Dead cell with exactly 3 neighbors becomes alive
-    // 3. All other cells die or stay dead
-    match (alive, neighbors) {
-        (true, 2) | (true, 3) => true,
-        (false, 3) => true,
-        _ => false,
+    if alive {
+        rule.survive[neighbors]
+    } else {
+        rule.birth[neighbors]
     }
 }
 
 /// Create next generation grid from current grid
-fn next_generation(grid: &[bool], width: usize, height: usize) -> Vec<bool> {
+fn next_generation(grid: &[bool], width: usize, height: usize, rule: &Rule, boundary: &Boundary) -> Vec<bool> {
     (0..height)
         .flat_map(|row| {
-            (0..width).map(move |col| cell_next_state(grid, row as isize, col as isize, width, height))
+            (0..width)
+                .map(move |col| cell_next_state(grid, row as isize, col as isize, width, height, rule, boundary))
         })
         .collect()
 }
 
-fn render_grid(frame: &mut Frame, grid: &[bool], width: usize, height: usize) {
+/// Overlay shown in the status bar: generation count, population, active rule, and
+/// effective ticks/sec, plus an optional stagnation message
+struct Status<'a> {
+    generation: u64,
+    population: usize,
+    rule_label: &'a str,
+    ticks_per_sec: f64,
+    message: Option<&'a str>,
+}
+
+fn render_grid(frame: &mut Frame, grid: &[bool], width: usize, height: usize, status: Option<&Status>) {
     let area = frame.area();
-    
+
+    let (grid_area, status_area) = match status {
+        Some(_) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(area);
+            (chunks[0], Some(chunks[1]))
+        }
+        None => (area, None),
+    };
+
     let mut content = String::new();
-    for row in 0..area.height as usize {
-        for col in 0..area.width as usize {
+    for row in 0..grid_area.height as usize {
+        for col in 0..grid_area.width as usize {
             if row < height && col < width {
                 if grid[pos(row, col, width)] {
                     content.push('â–ˆ'); // Full block for true
@@ -77,47 +198,431 @@ fn render_grid(frame: &mut Frame, grid: &[bool], width: usize, height: usize) {
                 content.push(' ');
             }
         }
-        if row < area.height as usize - 1 {
+        if row < grid_area.height as usize - 1 {
             content.push('\n');
         }
     }
-    
-    let paragraph = Paragraph::new(content);
-    frame.render_widget(paragraph, area);
+
+    frame.render_widget(Paragraph::new(content), grid_area);
+
+    if let (Some(status), Some(status_area)) = (status, status_area) {
+        let line = format!(
+            "gen {}  pop {}  rule {}  {:.1} ticks/s{}",
+            status.generation,
+            status.population,
+            status.rule_label,
+            status.ticks_per_sec,
+            status.message.map(|m| format!("  [{}]", m)).unwrap_or_default(),
+        );
+        frame.render_widget(Paragraph::new(line), status_area);
+    }
 }
 
-fn main() -> io::Result<()> {
-    // Setup terminal
-    enable_raw_mode()?;
-    io::stdout().execute(EnterAlternateScreen)?;
-    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+/// Build a fresh sparse universe, either from an already-loaded pattern (stamped at the
+/// universe center) or by randomizing the visible viewport
+#[allow(clippy::too_many_arguments)]
+fn seed_sparse(
+    pattern: Option<&pattern::Pattern>,
+    universe_width: i64,
+    universe_height: i64,
+    view_row: i64,
+    view_col: i64,
+    view_width: usize,
+    view_height: usize,
+) -> SparseGrid {
+    match pattern {
+        Some(loaded) => {
+            let mut world = SparseGrid::new(universe_width, universe_height);
+            let row_offset = universe_height / 2 - loaded.height as i64 / 2;
+            let col_offset = universe_width / 2 - loaded.width as i64 / 2;
+            for row in 0..loaded.height {
+                for col in 0..loaded.width {
+                    if loaded.cells[row * loaded.width + col] {
+                        world.set(row_offset + row as i64, col_offset + col as i64);
+                    }
+                }
+            }
+            world
+        }
+        None => random_sparse(universe_width, universe_height, view_row, view_col, view_width, view_height),
+    }
+}
 
-    // Get terminal size and create randomized flat array of bools
-    let size = terminal.size()?;
-    let width = size.width as usize;
-    let height = size.height as usize;
+/// Build a universe for on-demand reseeding (the `r` key, or `--auto-reseed`): always
+/// randomizes the viewport, regardless of any `--pattern` that seeded the initial universe
+fn random_sparse(
+    universe_width: i64,
+    universe_height: i64,
+    view_row: i64,
+    view_col: i64,
+    view_width: usize,
+    view_height: usize,
+) -> SparseGrid {
+    let mut world = SparseGrid::new(universe_width, universe_height);
+    let mut rng = rand::rng();
+    for row in 0..view_height as i64 {
+        for col in 0..view_width as i64 {
+            if rng.random_bool(0.5) {
+                world.set(view_row + row, view_col + col);
+            }
+        }
+    }
+    world
+}
+
+/// Run the simulation on the sparse engine: only live coordinates are tracked, so a
+/// small pattern stays cheap no matter how large `--universe` is. Mirrors `run_dense`'s
+/// pause/step/speed/reseed controls.
+fn run_sparse(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    args: &Args,
+    pattern: Option<&pattern::Pattern>,
+    universe_width: i64,
+    universe_height: i64,
+    view_width: usize,
+    view_height: usize,
+) -> io::Result<()> {
+    let view_row = universe_height / 2 - view_height as i64 / 2;
+    let view_col = universe_width / 2 - view_width as i64 / 2;
+    let mut world = seed_sparse(
+        pattern,
+        universe_width,
+        universe_height,
+        view_row,
+        view_col,
+        view_width,
+        view_height,
+    );
+    let mut paused = false;
+    let mut speed: f64 = 30.0;
+    let mut generation: u64 = 0;
+    let mut stagnation_message: Option<String> = None;
+    let mut history: VecDeque<u64> = VecDeque::with_capacity(4);
+    let mut last_tick = Instant::now();
+    let mut ticks_per_sec = 0.0;
+
+    loop {
+        let dense = world.to_dense(view_row, view_col, view_width, view_height);
+        terminal.draw(|frame| {
+            let status = Status {
+                generation,
+                population: world.live.len(),
+                rule_label: &args.rule.label,
+                ticks_per_sec,
+                message: stagnation_message.as_deref(),
+            };
+            render_grid(frame, &dense, view_width, view_height, Some(&status));
+        })?;
+
+        let frame_duration = Duration::from_secs_f64(1.0 / speed);
+
+        // Poll for key press with timeout
+        if event::poll(frame_duration)? {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Char(' ') => {
+                        paused = !paused;
+                        stagnation_message = None;
+                        history.clear();
+                    }
+                    KeyCode::Char('n') if paused => {
+                        world.step(&args.rule, &args.boundary);
+                        generation += 1;
+                        check_stagnation_sparse(
+                            &mut world,
+                            &mut history,
+                            &mut generation,
+                            args,
+                            universe_width,
+                            universe_height,
+                            view_row,
+                            view_col,
+                            view_width,
+                            view_height,
+                            &mut paused,
+                            &mut stagnation_message,
+                        );
+                    }
+                    KeyCode::Char('+') => speed = (speed + 1.0).min(60.0),
+                    KeyCode::Char('-') => speed = (speed - 1.0).max(1.0),
+                    KeyCode::Char('r') => {
+                        world = random_sparse(
+                            universe_width,
+                            universe_height,
+                            view_row,
+                            view_col,
+                            view_width,
+                            view_height,
+                        );
+                        generation = 0;
+                        history.clear();
+                        stagnation_message = None;
+                        paused = false;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if !paused {
+            world.step(&args.rule, &args.boundary);
+            generation += 1;
+            check_stagnation_sparse(
+                &mut world,
+                &mut history,
+                &mut generation,
+                args,
+                universe_width,
+                universe_height,
+                view_row,
+                view_col,
+                view_width,
+                view_height,
+                &mut paused,
+                &mut stagnation_message,
+            );
+        }
+
+        let now = Instant::now();
+        ticks_per_sec = if paused {
+            0.0
+        } else {
+            1.0 / now.duration_since(last_tick).as_secs_f64().max(f64::EPSILON)
+        };
+        last_tick = now;
+    }
+
+    Ok(())
+}
+
+/// Fill a fresh grid, either from an already-loaded pattern or randomly
+fn seed_grid(pattern: Option<&pattern::Pattern>, width: usize, height: usize) -> Vec<bool> {
+    match pattern {
+        Some(loaded) => {
+            let mut grid = vec![false; width * height];
+            pattern::stamp_pattern(&mut grid, width, height, loaded);
+            grid
+        }
+        None => random_grid(width, height),
+    }
+}
+
+/// Build a grid for on-demand reseeding (the `r` key, or `--auto-reseed`): always
+/// randomized, regardless of any `--pattern` that seeded the initial grid. Otherwise a
+/// loaded pattern "reseeds" to the exact same frozen state every time it goes stagnant.
+fn random_grid(width: usize, height: usize) -> Vec<bool> {
     let mut rng = rand::rng();
-    let mut grid: Vec<bool> = (0..width * height).map(|_| rng.random_bool(0.5)).collect();
+    (0..width * height).map(|_| rng.random_bool(0.5)).collect()
+}
+
+/// Run the simulation on the dense engine, with pause/step/speed/reseed controls
+fn run_dense(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    args: &Args,
+    pattern: Option<&pattern::Pattern>,
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let mut grid = seed_grid(pattern, width, height);
+    let mut paused = false;
+    let mut speed: f64 = 30.0;
+    let mut generation: u64 = 0;
+    let mut stagnation_message: Option<String> = None;
+    // Ring buffer of the last few generation hashes, used to spot still lifes and
+    // low-period oscillators
+    let mut history: VecDeque<u64> = VecDeque::with_capacity(4);
+    let mut last_tick = Instant::now();
+    let mut ticks_per_sec = 0.0;
 
-    // Main loop
-    let frame_duration = Duration::from_secs_f64(1.0 / 30.0);
-    
     loop {
         terminal.draw(|frame| {
-            render_grid(frame, &grid, width, height);
+            let status = Status {
+                generation,
+                population: grid.iter().filter(|&&alive| alive).count(),
+                rule_label: &args.rule.label,
+                ticks_per_sec,
+                message: stagnation_message.as_deref(),
+            };
+            render_grid(frame, &grid, width, height, Some(&status));
         })?;
 
+        let frame_duration = Duration::from_secs_f64(1.0 / speed);
+
         // Poll for key press with timeout
         if event::poll(frame_duration)? {
             if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') || key.code == KeyCode::Esc {
-                    break;
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Char(' ') => {
+                        paused = !paused;
+                        stagnation_message = None;
+                        // Otherwise the hash window still holds the frozen generation and
+                        // immediately re-matches on the very next tick
+                        history.clear();
+                    }
+                    KeyCode::Char('n') if paused => {
+                        grid = next_generation(&grid, width, height, &args.rule, &args.boundary);
+                        generation += 1;
+                        check_stagnation(&mut grid, &mut history, &mut generation, args, width, height, &mut paused, &mut stagnation_message);
+                    }
+                    KeyCode::Char('+') => speed = (speed + 1.0).min(60.0),
+                    KeyCode::Char('-') => speed = (speed - 1.0).max(1.0),
+                    KeyCode::Char('r') => {
+                        grid = random_grid(width, height);
+                        generation = 0;
+                        history.clear();
+                        stagnation_message = None;
+                        paused = false;
+                    }
+                    _ => {}
                 }
             }
         }
 
-        // Advance to next generation
-        grid = next_generation(&grid, width, height);
+        if !paused {
+            // Advance to next generation
+            grid = next_generation(&grid, width, height, &args.rule, &args.boundary);
+            generation += 1;
+            check_stagnation(&mut grid, &mut history, &mut generation, args, width, height, &mut paused, &mut stagnation_message);
+        }
+
+        let now = Instant::now();
+        ticks_per_sec = if paused {
+            0.0
+        } else {
+            1.0 / now.duration_since(last_tick).as_secs_f64().max(f64::EPSILON)
+        };
+        last_tick = now;
+    }
+
+    Ok(())
+}
+
+/// Record a new generation hash in the ring buffer and report the oscillation period if it
+/// matches a hash already in the window
+fn detect_period(history: &mut VecDeque<u64>, new_hash: u64) -> Option<usize> {
+    let period = history.iter().rev().position(|&h| h == new_hash).map(|i| i + 1);
+
+    if history.len() == 4 {
+        history.pop_front();
+    }
+    history.push_back(new_hash);
+
+    period
+}
+
+/// Detect still lifes and low-period oscillators from the recent generation-hash history,
+/// and either freeze with a status message or reseed (with `--auto-reseed`)
+#[allow(clippy::too_many_arguments)]
+fn check_stagnation(
+    grid: &mut Vec<bool>,
+    history: &mut VecDeque<u64>,
+    generation: &mut u64,
+    args: &Args,
+    width: usize,
+    height: usize,
+    paused: &mut bool,
+    status: &mut Option<String>,
+) {
+    if let Some(period) = detect_period(history, hash_grid(grid)) {
+        if args.auto_reseed {
+            *grid = random_grid(width, height);
+            *generation = 0;
+            history.clear();
+            *status = None;
+        } else {
+            *status = Some(if period == 1 {
+                format!("stable after {} gens", generation)
+            } else {
+                format!("oscillating (period {}) after {} gens", period, generation)
+            });
+            *paused = true;
+        }
+    }
+}
+
+/// Sparse-engine counterpart to `check_stagnation`, hashing the live set instead of a
+/// dense grid
+#[allow(clippy::too_many_arguments)]
+fn check_stagnation_sparse(
+    world: &mut SparseGrid,
+    history: &mut VecDeque<u64>,
+    generation: &mut u64,
+    args: &Args,
+    universe_width: i64,
+    universe_height: i64,
+    view_row: i64,
+    view_col: i64,
+    view_width: usize,
+    view_height: usize,
+    paused: &mut bool,
+    status: &mut Option<String>,
+) {
+    if let Some(period) = detect_period(history, world.hash_live()) {
+        if args.auto_reseed {
+            *world = random_sparse(
+                universe_width,
+                universe_height,
+                view_row,
+                view_col,
+                view_width,
+                view_height,
+            );
+            *generation = 0;
+            history.clear();
+            *status = None;
+        } else {
+            *status = Some(if period == 1 {
+                format!("stable after {} gens", generation)
+            } else {
+                format!("oscillating (period {}) after {} gens", period, generation)
+            });
+            *paused = true;
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let args = parse_args();
+
+    // Load the pattern file (if any) before entering raw mode, so a bad path or
+    // malformed file reports a visible error instead of silently yielding a blank grid
+    let loaded_pattern = match &args.pattern {
+        Some(path) => match pattern::load_pattern(path) {
+            Ok(loaded) => Some(loaded),
+            Err(e) => {
+                eprintln!("failed to load pattern {}: {}", path.display(), e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    // Setup terminal
+    enable_raw_mode()?;
+    io::stdout().execute(EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    // Get terminal size
+    let size = terminal.size()?;
+    let width = size.width as usize;
+    // Reserve the bottom row for the status bar, so the grid height we simulate matches
+    // what render_grid actually has room to draw
+    let height = (size.height as usize).saturating_sub(1);
+
+    if let Some((universe_width, universe_height)) = args.universe {
+        run_sparse(
+            &mut terminal,
+            &args,
+            loaded_pattern.as_ref(),
+            universe_width,
+            universe_height,
+            width,
+            height,
+        )?;
+    } else {
+        run_dense(&mut terminal, &args, loaded_pattern.as_ref(), width, height)?;
     }
 
     // Restore terminal
@@ -126,3 +631,41 @@ fn main() -> io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_period_reports_still_life_as_period_one() {
+        let mut history = VecDeque::with_capacity(4);
+        assert_eq!(detect_period(&mut history, 1), None);
+        assert_eq!(detect_period(&mut history, 1), Some(1));
+    }
+
+    #[test]
+    fn detect_period_reports_oscillator_period() {
+        let mut history = VecDeque::with_capacity(4);
+        assert_eq!(detect_period(&mut history, 1), None);
+        assert_eq!(detect_period(&mut history, 2), None);
+        assert_eq!(detect_period(&mut history, 1), Some(2));
+    }
+
+    #[test]
+    fn detect_period_is_none_while_the_grid_keeps_changing() {
+        let mut history = VecDeque::with_capacity(4);
+        assert_eq!(detect_period(&mut history, 1), None);
+        assert_eq!(detect_period(&mut history, 2), None);
+        assert_eq!(detect_period(&mut history, 3), None);
+        assert_eq!(detect_period(&mut history, 4), None);
+    }
+
+    #[test]
+    fn detect_period_window_does_not_grow_past_capacity() {
+        let mut history = VecDeque::with_capacity(4);
+        for hash in 1..=10 {
+            detect_period(&mut history, hash);
+        }
+        assert_eq!(history.len(), 4);
+    }
+}
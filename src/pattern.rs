@@ -0,0 +1,174 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A pattern loaded from a file: its own dimensions and the live cells within them
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<bool>,
+}
+
+/// Load a pattern file, dispatching on extension: `.rle` is run-length encoded,
+/// anything else is treated as the plaintext `.cells` format
+pub fn load_pattern(path: &Path) -> io::Result<Pattern> {
+    let contents = fs::read_to_string(path)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rle") => parse_rle(&contents),
+        _ => Ok(parse_plaintext(&contents)),
+    }
+}
+
+/// Parse the plaintext format: `.`/space is dead, any other printable char is alive,
+/// lines starting with `!` are comments
+fn parse_plaintext(contents: &str) -> Pattern {
+    let rows: Vec<&str> = contents
+        .lines()
+        .filter(|line| !line.starts_with('!'))
+        .collect();
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let height = rows.len();
+    let mut cells = vec![false; width * height];
+
+    for (row, line) in rows.iter().enumerate() {
+        for (col, ch) in line.chars().enumerate() {
+            if ch != '.' && ch != ' ' {
+                cells[row * width + col] = true;
+            }
+        }
+    }
+
+    Pattern { width, height, cells }
+}
+
+/// Parse the RLE format: a `x = m, y = n, rule = ...` header followed by a body of
+/// run-length tokens (`<count>b`, `<count>o`, `<count>$`), terminated by `!`
+fn parse_rle(contents: &str) -> io::Result<Pattern> {
+    let mut width = 0usize;
+    let mut height = 0usize;
+    let mut body = String::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('x') {
+            for field in line.split(',') {
+                let field = field.trim();
+                if let Some(value) = field.trim_start_matches('x').trim_start().strip_prefix('=') {
+                    width = value.trim().parse().unwrap_or(0);
+                } else if let Some(value) = field.trim_start_matches('y').trim_start().strip_prefix('=') {
+                    height = value.trim().parse().unwrap_or(0);
+                }
+            }
+            continue;
+        }
+        body.push_str(line);
+    }
+
+    let mut cells = vec![false; width * height];
+    let mut row = 0usize;
+    let mut col = 0usize;
+    let mut count = String::new();
+
+    for ch in body.chars() {
+        match ch {
+            '0'..='9' => count.push(ch),
+            'b' | 'o' | '$' => {
+                let run = count.parse::<usize>().unwrap_or(1);
+                count.clear();
+                match ch {
+                    'b' => col += run,
+                    'o' => {
+                        for _ in 0..run {
+                            if row < height && col < width {
+                                cells[row * width + col] = true;
+                            }
+                            col += 1;
+                        }
+                    }
+                    '$' => {
+                        row += run;
+                        col = 0;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            '!' => break,
+            _ => {}
+        }
+    }
+
+    Ok(Pattern { width, height, cells })
+}
+
+/// Stamp a pattern into the center of a larger grid, clipping anything that doesn't fit
+pub fn stamp_pattern(grid: &mut [bool], grid_width: usize, grid_height: usize, pattern: &Pattern) {
+    let row_offset = grid_height.saturating_sub(pattern.height) / 2;
+    let col_offset = grid_width.saturating_sub(pattern.width) / 2;
+
+    for row in 0..pattern.height {
+        for col in 0..pattern.width {
+            if !pattern.cells[row * pattern.width + col] {
+                continue;
+            }
+            let r = row_offset + row;
+            let c = col_offset + col;
+            if r < grid_height && c < grid_width {
+                grid[r * grid_width + c] = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plaintext_with_comments_and_ragged_rows() {
+        let contents = "!Name: glider\n.O\n..O\nOOO\n";
+        let pattern = parse_plaintext(contents);
+
+        assert_eq!(pattern.width, 3);
+        assert_eq!(pattern.height, 3);
+        #[rustfmt::skip]
+        assert_eq!(pattern.cells, vec![
+            false, true,  false,
+            false, false, true,
+            true,  true,  true,
+        ]);
+    }
+
+    #[test]
+    fn parses_rle_glider() {
+        let contents = "x = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+        let pattern = parse_rle(contents).unwrap();
+
+        assert_eq!(pattern.width, 3);
+        assert_eq!(pattern.height, 3);
+        #[rustfmt::skip]
+        assert_eq!(pattern.cells, vec![
+            false, true,  false,
+            false, false, true,
+            true,  true,  true,
+        ]);
+    }
+
+    #[test]
+    fn stamps_pattern_into_center_of_larger_grid() {
+        let pattern = Pattern {
+            width: 2,
+            height: 1,
+            cells: vec![true, true],
+        };
+        let mut grid = vec![false; 4 * 4];
+        stamp_pattern(&mut grid, 4, 4, &pattern);
+
+        assert!(grid[4 + 1]);
+        assert!(grid[4 + 2]);
+        assert_eq!(grid.iter().filter(|&&c| c).count(), 2);
+    }
+}
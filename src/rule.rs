@@ -0,0 +1,76 @@
+/// A Life-like rule: which neighbor counts trigger a birth, and which let a live cell survive
+pub struct Rule {
+    pub birth: [bool; 9],
+    pub survive: [bool; 9],
+    pub label: String,
+}
+
+impl Rule {
+    /// The standard Conway rule, B3/S23
+    pub fn conway() -> Rule {
+        Rule::parse("B3/S23").expect("B3/S23 is a valid rulestring")
+    }
+
+    /// Parse a `Bxx/Sxx` rulestring, e.g. `B3/S23` (Conway) or `B36/S23` (HighLife)
+    pub fn parse(s: &str) -> Option<Rule> {
+        let s = s.trim();
+        let (b_part, s_part) = s.split_once('/')?;
+        let digits = b_part.strip_prefix(['B', 'b'])?;
+        let counts = s_part.strip_prefix(['S', 's'])?;
+
+        let mut birth = [false; 9];
+        for ch in digits.chars() {
+            let n = ch.to_digit(10)? as usize;
+            *birth.get_mut(n)? = true;
+        }
+
+        let mut survive = [false; 9];
+        for ch in counts.chars() {
+            let n = ch.to_digit(10)? as usize;
+            *survive.get_mut(n)? = true;
+        }
+
+        Some(Rule {
+            birth,
+            survive,
+            label: s.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_conway_rule() {
+        let rule = Rule::parse("B3/S23").unwrap();
+        assert_eq!(rule.birth, [false, false, false, true, false, false, false, false, false]);
+        assert_eq!(rule.survive, [false, false, true, true, false, false, false, false, false]);
+        assert_eq!(rule.label, "B3/S23");
+    }
+
+    #[test]
+    fn parses_highlife_rule() {
+        let rule = Rule::parse("B36/S23").unwrap();
+        assert!(rule.birth[3] && rule.birth[6]);
+        assert!(!rule.birth[2] && !rule.birth[4] && !rule.birth[5]);
+        assert!(rule.survive[2] && rule.survive[3]);
+    }
+
+    #[test]
+    fn is_case_insensitive_and_trims_whitespace() {
+        let rule = Rule::parse("  b3/s23  ").unwrap();
+        assert!(rule.birth[3]);
+        assert!(rule.survive[2] && rule.survive[3]);
+    }
+
+    #[test]
+    fn rejects_malformed_rulestrings() {
+        assert!(Rule::parse("not a rule").is_none());
+        assert!(Rule::parse("B3").is_none());
+        assert!(Rule::parse("B3/X23").is_none());
+        // 9 neighbors is out of range - a cell only has 8
+        assert!(Rule::parse("B9/S23").is_none());
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use crate::boundary::Boundary;
+use crate::rule::Rule;
+
+/// A live-cell coordinate on the (possibly enormous) toroidal universe
+pub type Coord = (i64, i64);
+
+/// A sparse Life universe that only stores live coordinates, so simulation cost scales
+/// with population rather than with universe area
+pub struct SparseGrid {
+    pub width: i64,
+    pub height: i64,
+    pub live: HashSet<Coord>,
+}
+
+impl SparseGrid {
+    pub fn new(width: i64, height: i64) -> SparseGrid {
+        SparseGrid {
+            width,
+            height,
+            live: HashSet::new(),
+        }
+    }
+
+    /// Wrap a coordinate into the toroidal universe
+    fn wrap(&self, row: i64, col: i64) -> Coord {
+        (row.rem_euclid(self.height), col.rem_euclid(self.width))
+    }
+
+    /// Mark a cell alive, wrapping its coordinate onto the universe first
+    pub fn set(&mut self, row: i64, col: i64) {
+        let coord = self.wrap(row, col);
+        self.live.insert(coord);
+    }
+
+    /// Resolve a neighbor coordinate per the boundary mode: wrapped for `Toroidal`,
+    /// or `None` (uncounted, as if dead) when it falls outside the universe in `Dead` mode
+    fn neighbor_coord(&self, row: i64, col: i64, boundary: &Boundary) -> Option<Coord> {
+        match boundary {
+            Boundary::Toroidal => Some(self.wrap(row, col)),
+            Boundary::Dead => {
+                if row < 0 || col < 0 || row >= self.height || col >= self.width {
+                    None
+                } else {
+                    Some((row, col))
+                }
+            }
+        }
+    }
+
+    /// Advance to the next generation under the given rule and boundary mode
+    pub fn step(&mut self, rule: &Rule, boundary: &Boundary) {
+        let mut neighbor_counts: HashMap<Coord, usize> = HashMap::new();
+        // Seed every live cell with a 0 count first, so an isolated cell (no live
+        // neighbors) still shows up below and can survive under rules like S0
+        for &coord in &self.live {
+            neighbor_counts.entry(coord).or_insert(0);
+        }
+        for &(row, col) in &self.live {
+            for dr in -1..=1 {
+                for dc in -1..=1 {
+                    if dr == 0 && dc == 0 {
+                        continue;
+                    }
+                    if let Some(neighbor) = self.neighbor_coord(row + dr, col + dc, boundary) {
+                        *neighbor_counts.entry(neighbor).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        let mut next = HashSet::new();
+        for (coord, count) in neighbor_counts {
+            let alive = self.live.contains(&coord);
+            let survives = alive && rule.survive[count];
+            let born = !alive && rule.birth[count];
+            if survives || born {
+                next.insert(coord);
+            }
+        }
+
+        self.live = next;
+    }
+
+    /// Hash the live set deterministically, used to detect still lifes and low-period
+    /// oscillators. `HashSet` iteration order is unspecified, so the coordinates are sorted
+    /// first rather than hashing the set directly.
+    pub fn hash_live(&self) -> u64 {
+        let mut coords: Vec<&Coord> = self.live.iter().collect();
+        coords.sort_unstable();
+        let mut hasher = DefaultHasher::new();
+        coords.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Project the live set onto a dense viewport, for reuse of the existing renderer
+    pub fn to_dense(&self, view_row: i64, view_col: i64, view_width: usize, view_height: usize) -> Vec<bool> {
+        let mut dense = vec![false; view_width * view_height];
+        for &(row, col) in &self.live {
+            let r = row - view_row;
+            let c = col - view_col;
+            if r >= 0 && c >= 0 && (r as usize) < view_height && (c as usize) < view_width {
+                dense[r as usize * view_width + c as usize] = true;
+            }
+        }
+        dense
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blinker_oscillates_under_toroidal_boundary() {
+        let mut world = SparseGrid::new(5, 5);
+        // Vertical blinker through the center
+        world.set(1, 2);
+        world.set(2, 2);
+        world.set(3, 2);
+
+        let rule = Rule::conway();
+        world.step(&rule, &Boundary::Toroidal);
+
+        let expected: HashSet<Coord> = [(2, 1), (2, 2), (2, 3)].into_iter().collect();
+        assert_eq!(world.live, expected);
+    }
+
+    #[test]
+    fn dead_boundary_drops_births_past_the_edge() {
+        // A blinker sitting in the corner would wrap under Toroidal, but under Dead its
+        // off-universe neighbors are simply uncounted
+        let mut world = SparseGrid::new(3, 3);
+        world.set(0, 0);
+        world.set(0, 1);
+        world.set(0, 2);
+
+        let rule = Rule::conway();
+        world.step(&rule, &Boundary::Dead);
+
+        // Only the center cell of the row has two live neighbors both within bounds
+        let expected: HashSet<Coord> = [(0, 1), (1, 1)].into_iter().collect();
+        assert_eq!(world.live, expected);
+    }
+
+    #[test]
+    fn isolated_cell_survives_under_s0_rule() {
+        let mut world = SparseGrid::new(5, 5);
+        world.set(2, 2);
+
+        let rule = Rule::parse("B3/S023").expect("B3/S023 is a valid rulestring");
+        world.step(&rule, &Boundary::Toroidal);
+
+        let expected: HashSet<Coord> = [(2, 2)].into_iter().collect();
+        assert_eq!(world.live, expected);
+    }
+
+    #[test]
+    fn isolated_cell_dies_without_s0_rule() {
+        let mut world = SparseGrid::new(5, 5);
+        world.set(2, 2);
+
+        let rule = Rule::conway();
+        world.step(&rule, &Boundary::Toroidal);
+
+        assert!(world.live.is_empty());
+    }
+}